@@ -0,0 +1,47 @@
+//! Carbs-required estimation
+//!
+//! When the zero-temp BG prediction still trends below the low threshold,
+//! suspending the basal alone won't be enough — the user needs to eat.
+//! `carbs_required` turns that shortfall into an actionable "eat N g
+//! carbs" estimate.
+
+use crate::types::Profile;
+
+/// Carbs (g) needed to bring the zero-temp prediction's minimum back up to
+/// `target_bg`, or `None` if that minimum doesn't drop below `threshold`
+/// (carbs_req is only reported when it would actually be meaningful).
+pub fn carbs_required(profile: &Profile, target_bg: f64, threshold: f64, min_predicted_bg: f64) -> Option<f64> {
+    if min_predicted_bg >= threshold {
+        return None;
+    }
+
+    // Carb sensitivity factor: mg/dL rise per gram of carb
+    let csf = profile.sens / profile.carb_ratio;
+    let carbs_req = ((target_bg - min_predicted_bg) / csf).max(0.0);
+
+    if carbs_req > 0.0 {
+        Some(carbs_req)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> Profile {
+        Profile::builder().sens(50.0).carb_ratio(10.0).build()
+    }
+
+    #[test]
+    fn no_carbs_required_when_prediction_stays_above_threshold() {
+        assert_eq!(carbs_required(&profile(), 100.0, 70.0, 80.0), None);
+    }
+
+    #[test]
+    fn estimates_carbs_from_the_shortfall_below_target() {
+        // csf = 50/10 = 5 mg/dL per gram; (100 - 50) / 5 = 10g
+        assert_eq!(carbs_required(&profile(), 100.0, 70.0, 50.0), Some(10.0));
+    }
+}
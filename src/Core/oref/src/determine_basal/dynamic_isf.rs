@@ -0,0 +1,42 @@
+//! Dynamic ISF: scale the profile's static sensitivity by estimated TDD
+//!
+//! A simplified port of AndroidAPS's reworked 1800-rule: the profile's
+//! configured ISF is treated as the value that's correct at a reference
+//! TDD (derived from the classic `1800 / TDD` rule for this insulin
+//! curve's peak), and scaled by how today's estimated TDD compares to
+//! that reference.
+
+use crate::tdd::Tdd;
+use crate::types::Profile;
+
+/// Variable (dynamic) sensitivity and the ratio it represents relative to
+/// the profile's static ISF
+#[derive(Debug, Clone, Copy)]
+pub struct DynamicSensitivity {
+    /// The TDD-scaled ISF (mg/dL per unit)
+    pub variable_sens: f64,
+
+    /// `variable_sens / profile.sens`
+    pub sensitivity_ratio: f64,
+}
+
+/// Compute dynamic sensitivity for the given profile and TDD estimate.
+/// Falls back to the profile's static `sens` (a 1.0 ratio) when there's no
+/// usable TDD yet.
+pub fn variable_sensitivity(profile: &Profile, tdd: &Tdd) -> DynamicSensitivity {
+    if tdd.total <= 0.0 {
+        return DynamicSensitivity {
+            variable_sens: profile.sens,
+            sensitivity_ratio: 1.0,
+        };
+    }
+
+    let insulin_peak = profile.curve.default_peak() as f64;
+    let reference_tdd = 1800.0 / profile.sens * (insulin_peak / 75.0);
+    let variable_sens = profile.sens * (reference_tdd / tdd.total);
+
+    DynamicSensitivity {
+        variable_sens,
+        sensitivity_ratio: variable_sens / profile.sens,
+    }
+}
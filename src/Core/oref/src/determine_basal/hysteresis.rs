@@ -0,0 +1,86 @@
+//! Temp-basal hysteresis
+//!
+//! Issuing a new temp basal command that's barely different from the one
+//! already running just churns the pump and fills the loop log with noise.
+//! `set_temp_basal` mirrors oref0's `setTempBasal`: when the current temp
+//! still has plenty of time left and the new suggestion is close enough,
+//! it leaves the running temp alone.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::DetermineBasalResult;
+
+/// A temp basal currently running on the pump
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CurrentTemp {
+    /// Rate of the running temp (U/hr)
+    pub rate: f64,
+
+    /// Minutes remaining before the running temp expires
+    pub duration: u32,
+}
+
+/// Minutes a current temp must have left before hysteresis kicks in
+const MIN_REMAINING_MINUTES: u32 = 20;
+
+/// Decide whether `suggested` is worth sending to the pump given the temp
+/// that's already running. If the current temp has more than
+/// [`MIN_REMAINING_MINUTES`] left and `suggested`'s rate is within +/-20%
+/// of it, the running temp is left in place: `rate`/`duration` are cleared
+/// and an explanatory note is appended to `reason`.
+pub fn set_temp_basal(current_temp: Option<CurrentTemp>, suggested: DetermineBasalResult) -> DetermineBasalResult {
+    let Some(current) = current_temp else {
+        return suggested;
+    };
+    let Some(suggested_rate) = suggested.rate else {
+        return suggested;
+    };
+
+    let close_enough = suggested_rate <= current.rate * 1.2 && suggested_rate >= current.rate * 0.8;
+    if current.duration <= MIN_REMAINING_MINUTES || !close_enough {
+        return suggested;
+    }
+
+    let mut result = suggested;
+    result.reason.push_str(&format!(
+        "; no temp required, current temp {:.3} U/hr with {}m left is close enough to suggested {:.3} U/hr",
+        current.rate, current.duration, suggested_rate
+    ));
+    result.rate = None;
+    result.duration = None;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestion(rate: f64) -> DetermineBasalResult {
+        DetermineBasalResult::temp_basal(rate, 30, "suggested")
+    }
+
+    #[test]
+    fn keeps_the_running_temp_when_close_enough() {
+        let current = CurrentTemp { rate: 1.0, duration: 25 };
+        let result = set_temp_basal(Some(current), suggestion(1.15));
+        assert!(!result.has_temp());
+        assert!(result.reason.contains("no temp required"));
+    }
+
+    #[test]
+    fn issues_a_new_temp_when_too_far_off() {
+        let current = CurrentTemp { rate: 1.0, duration: 25 };
+        let result = set_temp_basal(Some(current), suggestion(1.3));
+        assert_eq!(result.rate, Some(1.3));
+    }
+
+    #[test]
+    fn issues_a_new_temp_when_the_current_one_is_about_to_expire() {
+        let current = CurrentTemp { rate: 1.0, duration: 15 };
+        let result = set_temp_basal(Some(current), suggestion(1.1));
+        assert_eq!(result.rate, Some(1.1));
+    }
+}
@@ -0,0 +1,90 @@
+//! Main dosing algorithm
+//!
+//! This module ports oref0's `determine-basal`: given the current glucose,
+//! IOB/COB, and profile, it decides whether a new temp basal (or SMB)
+//! should be issued.
+
+mod carbs_required;
+mod dynamic_isf;
+mod hysteresis;
+mod pending_insulin;
+mod safety;
+
+pub use carbs_required::carbs_required;
+pub use dynamic_isf::{variable_sensitivity, DynamicSensitivity};
+pub use hysteresis::{set_temp_basal, CurrentTemp};
+pub use pending_insulin::{pending_insulin, LastTempBasal};
+pub use safety::get_max_safe_basal;
+
+use crate::tdd;
+use crate::types::{DetermineBasalResult, Profile, Treatment};
+
+/// Compute a temp basal recommendation for the current glucose/IOB state.
+///
+/// `current_basal` is the rate currently scheduled (used to derive the
+/// max-safe-basal ceiling); `current_temp` is the temp already running on
+/// the pump, if any; `eventual_bg`, `target_bg`, `threshold` and
+/// `min_predicted_bg` (the zero-temp prediction's low point) are all in
+/// mg/dL internally; `raw_rate` is the rate the upstream dosing math came
+/// up with before safety clamping and pump rounding; `raw_insulin_req` is
+/// the insulin need computed from `eventual_bg` before netting out
+/// `last_temp`/`unconfirmed_bolus`; `treatments` and `clock` (ms since Unix
+/// epoch) drive the TDD estimate behind dynamic ISF.
+///
+/// BG-valued fields of the returned result, and the `reason` string, are
+/// presented in `profile.out_units` (mg/dL or mmol/L) rather than the
+/// mg/dL used for all internal math.
+#[allow(clippy::too_many_arguments)]
+pub fn determine_basal(
+    profile: &Profile,
+    current_basal: f64,
+    current_temp: Option<CurrentTemp>,
+    eventual_bg: f64,
+    target_bg: f64,
+    threshold: f64,
+    min_predicted_bg: f64,
+    raw_rate: f64,
+    raw_insulin_req: f64,
+    last_temp: Option<LastTempBasal>,
+    unconfirmed_bolus: f64,
+    duration: u32,
+    treatments: &[Treatment],
+    clock: i64,
+) -> DetermineBasalResult {
+    let max_safe_basal = get_max_safe_basal(profile, current_basal);
+    let clamped = raw_rate.max(0.0).min(max_safe_basal);
+    let rate = profile.pump_model.round_rate(clamped);
+
+    let comparison = if eventual_bg >= target_bg { ">=" } else { "<" };
+    let mut reason = format!(
+        "Eventual BG {} {} target {}, rate {:.3} set ({:.3} rounded for {:?})",
+        profile.out_units.format(eventual_bg),
+        comparison,
+        profile.out_units.format(target_bg),
+        rate,
+        raw_rate,
+        profile.pump_model
+    );
+    if raw_rate < 0.0 {
+        reason.push_str(", negative rate clamped to 0");
+    } else if raw_rate > max_safe_basal {
+        reason.push_str(&format!(", limited by max_safe_basal {:.3}", max_safe_basal));
+    }
+
+    let mut result = DetermineBasalResult::temp_basal(rate, duration, reason);
+    result.eventual_bg = profile.out_units.convert(eventual_bg);
+    result.target_bg = Some(profile.out_units.convert(target_bg));
+    result.threshold = Some(profile.out_units.convert(threshold));
+
+    let tdd = tdd::calculate(treatments, clock);
+    let dynamic_sens = variable_sensitivity(profile, &tdd);
+    result.variable_sens = Some(dynamic_sens.variable_sens);
+    result.sensitivity_ratio = Some(dynamic_sens.sensitivity_ratio);
+
+    let pending = pending_insulin(profile, last_temp, unconfirmed_bolus, clock);
+    result.insulin_req = Some(raw_insulin_req - pending);
+    result.carbs_req = carbs_required(profile, target_bg, threshold, min_predicted_bg)
+        .map(|g| g.round());
+
+    set_temp_basal(current_temp, result)
+}
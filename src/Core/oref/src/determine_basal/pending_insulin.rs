@@ -0,0 +1,81 @@
+//! Pending insulin: insulin already "in the pipe" that a fresh
+//! `insulin_req` shouldn't double-count.
+
+use crate::profile::basal_lookup;
+use crate::types::Profile;
+
+/// A temp basal segment that was (or still is) running
+#[derive(Debug, Clone, Copy)]
+pub struct LastTempBasal {
+    /// Rate of the temp (U/hr)
+    pub rate: f64,
+
+    /// When the temp started (ms since Unix epoch)
+    pub start: i64,
+
+    /// Scheduled duration of the temp (minutes)
+    pub duration_minutes: u32,
+
+    /// Minutes-past-midnight the temp started at, used to look up the
+    /// scheduled basal rate it overrode
+    pub start_minutes_of_day: u32,
+}
+
+/// Net insulin (units) already committed but not yet absorbed: the
+/// portion of the last temp basal that ran above (or below) the profile's
+/// scheduled basal, plus any bolus the pump hasn't confirmed as delivered
+/// yet.
+pub fn pending_insulin(
+    profile: &Profile,
+    last_temp: Option<LastTempBasal>,
+    unconfirmed_bolus: f64,
+    clock: i64,
+) -> f64 {
+    let temp_net = last_temp.map_or(0.0, |temp| {
+        let elapsed_minutes = ((clock - temp.start).max(0) as f64 / 60_000.0).min(temp.duration_minutes as f64);
+        let scheduled_rate = basal_lookup(profile, temp.start_minutes_of_day);
+        (temp.rate - scheduled_rate) * elapsed_minutes / 60.0
+    });
+
+    temp_net + unconfirmed_bolus
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ScheduleEntry;
+
+    fn profile() -> Profile {
+        Profile::builder()
+            .basal_schedule(vec![ScheduleEntry { minutes: 0, value: 1.0 }])
+            .build()
+    }
+
+    #[test]
+    fn nets_a_running_high_temp_against_scheduled_basal() {
+        let temp = LastTempBasal {
+            rate: 2.0,
+            start: 0,
+            duration_minutes: 30,
+            start_minutes_of_day: 0,
+        };
+        // 30 minutes at (2.0 - 1.0) U/hr above scheduled = 0.5 U pending
+        assert_eq!(pending_insulin(&profile(), Some(temp), 0.0, 30 * 60_000), 0.5);
+    }
+
+    #[test]
+    fn clamps_to_the_temps_duration_once_it_has_ended() {
+        let temp = LastTempBasal {
+            rate: 2.0,
+            start: 0,
+            duration_minutes: 30,
+            start_minutes_of_day: 0,
+        };
+        assert_eq!(pending_insulin(&profile(), Some(temp), 0.0, 60 * 60_000), 0.5);
+    }
+
+    #[test]
+    fn adds_any_unconfirmed_bolus() {
+        assert_eq!(pending_insulin(&profile(), None, 0.3, 0), 0.3);
+    }
+}
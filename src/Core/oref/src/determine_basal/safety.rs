@@ -0,0 +1,58 @@
+//! Safety guardrails applied to the determine-basal output
+
+use crate::profile::max_daily_basal;
+use crate::types::Profile;
+
+/// Compute the hard ceiling (U/hr) for any recommended temp basal rate.
+///
+/// Caps at `min(max_basal, max_daily_safety_multiplier * max_daily_basal,
+/// current_basal_safety_multiplier * current_basal)`. The two multipliers
+/// fall back to oref0's defaults of `3.0` and `4.0` respectively when
+/// unset or NaN in the profile.
+pub fn get_max_safe_basal(profile: &Profile, current_basal: f64) -> f64 {
+    let daily_multiplier = or_default(profile.max_daily_safety_multiplier, 3.0);
+    let current_multiplier = or_default(profile.current_basal_safety_multiplier, 4.0);
+
+    profile
+        .max_basal
+        .min(daily_multiplier * max_daily_basal(profile))
+        .min(current_multiplier * current_basal)
+}
+
+fn or_default(multiplier: f64, default: f64) -> f64 {
+    if multiplier.is_nan() || multiplier <= 0.0 {
+        default
+    } else {
+        multiplier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ScheduleEntry;
+
+    fn profile() -> Profile {
+        Profile::builder()
+            .basal_schedule(vec![ScheduleEntry { minutes: 0, value: 1.0 }])
+            .max_basal(5.0)
+            .build()
+    }
+
+    #[test]
+    fn falls_back_to_default_multipliers_when_unset() {
+        // Multipliers default to 3.0 and 4.0: ceilings are max_basal 5.0,
+        // 3.0 * 1.0 = 3.0 and 4.0 * 0.5 = 2.0, so the current-basal one wins
+        assert_eq!(get_max_safe_basal(&profile(), 0.5), 2.0);
+    }
+
+    #[test]
+    fn honors_an_explicit_multiplier() {
+        let profile = Profile::builder()
+            .basal_schedule(vec![ScheduleEntry { minutes: 0, value: 1.0 }])
+            .max_basal(5.0)
+            .max_daily_safety_multiplier(2.0)
+            .build();
+        assert_eq!(get_max_safe_basal(&profile, 0.5), 2.0 * 1.0);
+    }
+}
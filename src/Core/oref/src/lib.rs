@@ -49,6 +49,9 @@ pub mod meal;
 pub mod autosens;
 pub mod determine_basal;
 pub mod profile;
+pub mod pump;
+pub mod tdd;
+pub mod units;
 pub mod utils;
 pub mod error;
 
@@ -65,6 +68,7 @@ pub mod prelude {
     pub use crate::iob::calculate as calculate_iob;
     pub use crate::cob::calculate as calculate_cob;
     pub use crate::determine_basal::determine_basal;
+    pub use crate::pump::PumpModel;
     pub use crate::error::OrefError;
 }
 
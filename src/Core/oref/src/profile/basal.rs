@@ -0,0 +1,57 @@
+//! Basal rate schedule lookups
+
+use crate::types::Profile;
+
+/// Look up the scheduled basal rate (U/hr) in effect `minutes` past
+/// midnight, falling back to the schedule's first entry if `minutes` is
+/// before it, or to `0.0` if the schedule is empty.
+pub fn basal_lookup(profile: &Profile, minutes: u32) -> f64 {
+    profile
+        .basal_schedule
+        .iter()
+        .rev()
+        .find(|entry| entry.minutes <= minutes)
+        .or_else(|| profile.basal_schedule.first())
+        .map(|entry| entry.value)
+        .unwrap_or(0.0)
+}
+
+/// The highest basal rate anywhere in the day's schedule
+pub fn max_daily_basal(profile: &Profile) -> f64 {
+    profile
+        .basal_schedule
+        .iter()
+        .map(|entry| entry.value)
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ScheduleEntry;
+
+    fn profile_with_schedule() -> Profile {
+        Profile::builder()
+            .basal_schedule(vec![
+                ScheduleEntry { minutes: 0, value: 0.8 },
+                ScheduleEntry { minutes: 360, value: 1.2 },
+                ScheduleEntry { minutes: 1320, value: 0.6 },
+            ])
+            .build()
+    }
+
+    #[test]
+    fn looks_up_the_entry_in_effect() {
+        let profile = profile_with_schedule();
+        assert_eq!(basal_lookup(&profile, 0), 0.8);
+        assert_eq!(basal_lookup(&profile, 359), 0.8);
+        assert_eq!(basal_lookup(&profile, 360), 1.2);
+        assert_eq!(basal_lookup(&profile, 1319), 1.2);
+        assert_eq!(basal_lookup(&profile, 1320), 0.6);
+    }
+
+    #[test]
+    fn max_daily_basal_is_the_schedule_peak() {
+        assert_eq!(max_daily_basal(&profile_with_schedule()), 1.2);
+    }
+}
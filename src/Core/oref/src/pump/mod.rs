@@ -0,0 +1,73 @@
+//! Pump delivery models
+//!
+//! Physical pumps can only deliver a temp basal rate in fixed increments; a
+//! recommendation that doesn't land on one of these increments is silently
+//! rounded (or rejected outright) by the pump itself. `PumpModel` captures
+//! the increment schedule for supported pump families so the rest of the
+//! crate can round its output before it's ever sent to hardware.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Pump family, used to select the temp basal increment schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum PumpModel {
+    /// Medtronic x23/x54-style pumps: 0.025 U below 1 U/hr, 0.05 U from 1
+    /// up to 10 U/hr, 0.1 U at or above 10 U/hr
+    #[default]
+    MedtronicX23,
+
+    /// Omnipod-style pumps: fixed 0.05 U increment across the whole range
+    Omnipod,
+}
+
+impl PumpModel {
+    /// Round `rate` (U/hr) to the nearest increment this pump can actually
+    /// deliver, mirroring oref0's `round-basal`.
+    pub fn round_rate(&self, rate: f64) -> f64 {
+        if rate <= 0.0 {
+            return 0.0;
+        }
+
+        match self {
+            PumpModel::MedtronicX23 => {
+                if rate < 1.0 {
+                    round_to_steps_per_unit(rate, 40.0)
+                } else if rate < 10.0 {
+                    round_to_steps_per_unit(rate, 20.0)
+                } else {
+                    round_to_steps_per_unit(rate, 10.0)
+                }
+            }
+            PumpModel::Omnipod => round_to_steps_per_unit(rate, 20.0),
+        }
+    }
+}
+
+/// Round `rate` to the nearest `1 / steps_per_unit` increment, e.g.
+/// `steps_per_unit = 20.0` rounds to the nearest 0.05 U
+fn round_to_steps_per_unit(rate: f64, steps_per_unit: f64) -> f64 {
+    (rate * steps_per_unit).round() / steps_per_unit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn medtronic_increments_follow_the_published_schedule() {
+        assert_eq!(PumpModel::MedtronicX23.round_rate(0.0), 0.0);
+        assert_eq!(PumpModel::MedtronicX23.round_rate(0.4), 0.4);
+        assert_eq!(PumpModel::MedtronicX23.round_rate(0.41), 0.4);
+        assert_eq!(PumpModel::MedtronicX23.round_rate(1.23), 1.25);
+        assert_eq!(PumpModel::MedtronicX23.round_rate(12.34), 12.3);
+    }
+
+    #[test]
+    fn omnipod_always_rounds_to_a_twentieth_of_a_unit() {
+        assert_eq!(PumpModel::Omnipod.round_rate(0.37), 0.35);
+        assert_eq!(PumpModel::Omnipod.round_rate(12.34), 12.35);
+    }
+}
@@ -0,0 +1,135 @@
+//! Total daily dose estimation
+//!
+//! Dynamic ISF needs a real estimate of total daily insulin (TDD) to scale
+//! sensitivity from. This mirrors AndroidAPS's Dynamic ISF TDD blend: an
+//! 8-hour window extrapolated to 24h, the last full calendar day, and a
+//! rolling 7-day average, combined as
+//! `TDD = 0.4 * tdd7 + 0.6 * tdd_pump_extrapolated`.
+
+use crate::types::Treatment;
+
+const HOUR_MS: i64 = 60 * 60 * 1000;
+const DAY_MS: i64 = 24 * HOUR_MS;
+const RECENT_WINDOW_HOURS: f64 = 8.0;
+
+/// Guardrail: below this many hours of recent history, the extrapolated
+/// pump TDD is too noisy to trust
+const MIN_RECENT_HOURS: f64 = 5.0;
+
+/// Total daily dose estimate, along with the windows it was built from
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tdd {
+    /// Blended total daily dose (units), ready for dynamic-ISF scaling
+    pub total: f64,
+
+    /// Rolling 7-day average TDD (units/day)
+    pub tdd7: f64,
+
+    /// TDD over the last full calendar day, UTC midnight to midnight (units)
+    pub tdd_daily: f64,
+
+    /// Recent pump activity (last 8h) extrapolated to a full day (units)
+    pub tdd_pump_extrapolated: f64,
+
+    /// Hours of treatment history available in the most recent window
+    pub recent_hours: f64,
+
+    /// Whether the guardrail fell back to `0.8 * tdd7` instead of the
+    /// blended estimate
+    pub fell_back: bool,
+}
+
+/// Estimate total daily dose from `treatments` as of `clock` (ms since the
+/// Unix epoch).
+pub fn calculate(treatments: &[Treatment], clock: i64) -> Tdd {
+    let tdd7 = delivered_insulin(treatments, clock, 7 * DAY_MS) / 7.0;
+    let today_start = clock - clock.rem_euclid(DAY_MS);
+    let tdd_daily = delivered_insulin(treatments, today_start, DAY_MS);
+
+    let recent_hours = hours_of_history(treatments, clock, (RECENT_WINDOW_HOURS * HOUR_MS as f64) as i64);
+    let tdd_8h = delivered_insulin(treatments, clock, (RECENT_WINDOW_HOURS * HOUR_MS as f64) as i64);
+    let tdd_pump_extrapolated = if recent_hours > 0.0 {
+        tdd_8h / recent_hours * 24.0
+    } else {
+        0.0
+    };
+
+    let too_fresh = recent_hours < MIN_RECENT_HOURS;
+    let implausible = is_implausible(tdd_pump_extrapolated, tdd7) || is_implausible(tdd_pump_extrapolated, tdd_daily);
+    let fell_back = too_fresh || implausible;
+
+    let total = if fell_back {
+        0.8 * tdd7
+    } else {
+        0.4 * tdd7 + 0.6 * tdd_pump_extrapolated
+    };
+
+    Tdd {
+        total,
+        tdd7,
+        tdd_daily,
+        tdd_pump_extrapolated,
+        recent_hours,
+        fell_back,
+    }
+}
+
+/// Whether `extrapolated` is more than 3x, or less than a quarter of,
+/// `reference` — used to flag an implausible pump-extrapolated TDD against
+/// either the 7-day average or the last full day's total.
+fn is_implausible(extrapolated: f64, reference: f64) -> bool {
+    reference > 0.0 && (extrapolated > reference * 3.0 || extrapolated < reference * 0.25)
+}
+
+/// Sum the insulin actually delivered (bolus + temp basal) within
+/// `(clock - window_ms, clock]`.
+fn delivered_insulin(treatments: &[Treatment], clock: i64, window_ms: i64) -> f64 {
+    let start = clock - window_ms;
+    treatments
+        .iter()
+        .filter(|t| t.date > start && t.date <= clock)
+        .map(Treatment::delivered_insulin)
+        .sum()
+}
+
+/// Hours of treatment history available since `clock - window_ms`
+fn hours_of_history(treatments: &[Treatment], clock: i64, window_ms: i64) -> f64 {
+    let start = clock - window_ms;
+    treatments
+        .iter()
+        .filter(|t| t.date > start && t.date <= clock)
+        .map(|t| (clock - t.date) as f64 / HOUR_MS as f64)
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bolus(hours_ago: i64, units: f64, clock: i64) -> Treatment {
+        Treatment {
+            date: clock - hours_ago * HOUR_MS,
+            insulin: Some(units),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn falls_back_when_history_is_too_fresh() {
+        let clock = 10 * DAY_MS;
+        let treatments = vec![bolus(1, 1.0, clock)];
+        let tdd = calculate(&treatments, clock);
+        assert!(tdd.fell_back);
+        assert_eq!(tdd.total, 0.8 * tdd.tdd7);
+    }
+
+    #[test]
+    fn blends_pump_and_weekly_tdd_when_data_is_plausible() {
+        let clock = 10 * DAY_MS;
+        let mut treatments: Vec<Treatment> = (1..=7).map(|d| bolus(d * 24, 40.0, clock)).collect();
+        treatments.extend((1..=8).map(|h| bolus(h, 40.0 / 24.0, clock)));
+        let tdd = calculate(&treatments, clock);
+        assert!(!tdd.fell_back);
+        assert_eq!(tdd.total, 0.4 * tdd.tdd7 + 0.6 * tdd.tdd_pump_extrapolated);
+    }
+}
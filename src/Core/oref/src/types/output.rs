@@ -27,7 +27,7 @@ pub struct DetermineBasalResult {
     #[cfg_attr(feature = "serde", serde(default))]
     pub iob: f64,
 
-    /// Eventual BG prediction (mg/dL)
+    /// Eventual BG prediction, in the profile's `out_units`
     #[cfg_attr(feature = "serde", serde(default))]
     pub eventual_bg: f64,
 
@@ -60,6 +60,7 @@ pub struct DetermineBasalResult {
     pub variable_sens: Option<f64>,
 
     // ============ Prediction Arrays (for visualization) ============
+    // All in the profile's `out_units`, same as `eventual_bg`.
     /// Predicted BG values
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub predicted_bg: Option<Vec<f64>>,
@@ -85,7 +86,7 @@ pub struct DetermineBasalResult {
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub bg_mins_ago: Option<f64>,
 
-    /// Target BG used
+    /// Target BG used, in the profile's `out_units`
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub target_bg: Option<f64>,
 
@@ -97,7 +98,7 @@ pub struct DetermineBasalResult {
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub carbs_req: Option<f64>,
 
-    /// Threshold BG
+    /// Threshold BG, in the profile's `out_units`
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub threshold: Option<f64>,
 }
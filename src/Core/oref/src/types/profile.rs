@@ -0,0 +1,166 @@
+//! Patient and pump configuration
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::insulin::InsulinCurve;
+use crate::pump::PumpModel;
+use crate::units::OutUnits;
+
+/// Patient and pump configuration used throughout the oref algorithms
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Profile {
+    /// Duration of insulin action (hours)
+    pub dia: f64,
+
+    /// Insulin sensitivity factor (mg/dL drop per unit)
+    pub sens: f64,
+
+    /// Carb ratio (grams of carb per unit of insulin)
+    pub carb_ratio: f64,
+
+    /// Insulin action curve
+    pub curve: InsulinCurve,
+
+    /// Pump model, used to round recommended rates to a deliverable
+    /// increment before they reach `DetermineBasalResult`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pump_model: PumpModel,
+
+    /// Scheduled basal rates (U/hr) by minutes-past-midnight, used to look
+    /// up both the currently-running basal and the day's maximum
+    pub basal_schedule: Vec<ScheduleEntry>,
+
+    /// Absolute ceiling on any recommended temp basal rate (U/hr), as
+    /// configured on the pump itself
+    pub max_basal: f64,
+
+    /// Multiplier applied to the day's highest scheduled basal rate to
+    /// derive a safety ceiling. Falls back to `3.0` when unset or NaN.
+    pub max_daily_safety_multiplier: f64,
+
+    /// Multiplier applied to the currently-running basal rate to derive a
+    /// safety ceiling. Falls back to `4.0` when unset or NaN.
+    pub current_basal_safety_multiplier: f64,
+
+    /// Units to present BG values in. Internal calculations always stay
+    /// in mg/dL regardless of this setting.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub out_units: OutUnits,
+}
+
+/// A single entry in a time-of-day schedule (basal, ISF, carb ratio, ...):
+/// the value that applies starting at `minutes` past midnight
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ScheduleEntry {
+    /// Minutes past midnight at which this entry takes effect
+    pub minutes: u32,
+
+    /// The scheduled value, e.g. U/hr for a basal entry
+    pub value: f64,
+}
+
+impl Profile {
+    /// Start building a new `Profile`
+    pub fn builder() -> ProfileBuilder {
+        ProfileBuilder::default()
+    }
+}
+
+/// Builder for [`Profile`]
+#[derive(Debug, Clone, Default)]
+pub struct ProfileBuilder {
+    dia: Option<f64>,
+    sens: Option<f64>,
+    carb_ratio: Option<f64>,
+    curve: InsulinCurve,
+    pump_model: PumpModel,
+    basal_schedule: Vec<ScheduleEntry>,
+    max_basal: Option<f64>,
+    max_daily_safety_multiplier: f64,
+    current_basal_safety_multiplier: f64,
+    out_units: OutUnits,
+}
+
+impl ProfileBuilder {
+    /// Set the duration of insulin action (hours)
+    pub fn dia(mut self, dia: f64) -> Self {
+        self.dia = Some(dia);
+        self
+    }
+
+    /// Set the insulin sensitivity factor (mg/dL per unit)
+    pub fn sens(mut self, sens: f64) -> Self {
+        self.sens = Some(sens);
+        self
+    }
+
+    /// Set the carb ratio (grams per unit)
+    pub fn carb_ratio(mut self, carb_ratio: f64) -> Self {
+        self.carb_ratio = Some(carb_ratio);
+        self
+    }
+
+    /// Set the insulin action curve
+    pub fn curve(mut self, curve: InsulinCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Set the pump model used to round recommended rates
+    pub fn pump_model(mut self, pump_model: PumpModel) -> Self {
+        self.pump_model = pump_model;
+        self
+    }
+
+    /// Set the scheduled basal rates (U/hr) by minutes-past-midnight
+    pub fn basal_schedule(mut self, basal_schedule: Vec<ScheduleEntry>) -> Self {
+        self.basal_schedule = basal_schedule;
+        self
+    }
+
+    /// Set the pump's absolute max basal rate (U/hr)
+    pub fn max_basal(mut self, max_basal: f64) -> Self {
+        self.max_basal = Some(max_basal);
+        self
+    }
+
+    /// Set the max-daily-basal safety multiplier (default `3.0`)
+    pub fn max_daily_safety_multiplier(mut self, multiplier: f64) -> Self {
+        self.max_daily_safety_multiplier = multiplier;
+        self
+    }
+
+    /// Set the current-basal safety multiplier (default `4.0`)
+    pub fn current_basal_safety_multiplier(mut self, multiplier: f64) -> Self {
+        self.current_basal_safety_multiplier = multiplier;
+        self
+    }
+
+    /// Set the units BG values are presented in (default mg/dL)
+    pub fn out_units(mut self, out_units: OutUnits) -> Self {
+        self.out_units = out_units;
+        self
+    }
+
+    /// Build the `Profile`, falling back to oref0 defaults for any field
+    /// that wasn't set
+    pub fn build(self) -> Profile {
+        Profile {
+            dia: self.dia.unwrap_or(5.0),
+            sens: self.sens.unwrap_or(50.0),
+            carb_ratio: self.carb_ratio.unwrap_or(10.0),
+            curve: self.curve,
+            pump_model: self.pump_model,
+            basal_schedule: self.basal_schedule,
+            max_basal: self.max_basal.unwrap_or(3.0),
+            max_daily_safety_multiplier: self.max_daily_safety_multiplier,
+            current_basal_safety_multiplier: self.current_basal_safety_multiplier,
+            out_units: self.out_units,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! Treatment history entries
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single treatment history entry: a bolus, a portion of a temp basal,
+/// or a carb entry. Mirrors the shape of a Nightscout treatment record.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Treatment {
+    /// When this treatment was delivered (ms since Unix epoch)
+    pub date: i64,
+
+    /// Bolus insulin delivered (units), if this is a bolus
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub insulin: Option<f64>,
+
+    /// Carbs entered (grams), if this is a carb entry
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub carbs: Option<f64>,
+
+    /// Temp basal rate (U/hr), if this is a temp basal segment
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub rate: Option<f64>,
+
+    /// Temp basal duration (minutes), if this is a temp basal segment
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub duration: Option<u32>,
+}
+
+impl Treatment {
+    /// Units of insulin this entry actually delivered: the bolus amount
+    /// plus whatever a temp basal segment works out to
+    pub fn delivered_insulin(&self) -> f64 {
+        let bolus = self.insulin.unwrap_or(0.0);
+        let basal = match (self.rate, self.duration) {
+            (Some(rate), Some(duration)) => rate * duration as f64 / 60.0,
+            _ => 0.0,
+        };
+        bolus + basal
+    }
+}
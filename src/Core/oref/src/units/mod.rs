@@ -0,0 +1,59 @@
+//! Output unit conversion
+//!
+//! Every BG value the crate computes internally is mg/dL. `OutUnits` lets
+//! a profile request the crate *present* BG values — reason strings and
+//! the BG-valued fields of `DetermineBasalResult` — in mmol/L instead, for
+//! EU users. Internal calculations always stay in mg/dL.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Units to format BG values in for display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum OutUnits {
+    /// Milligrams per deciliter (the crate's internal unit)
+    #[default]
+    MgDl,
+
+    /// Millimoles per liter
+    MmolL,
+}
+
+impl OutUnits {
+    /// Convert a mg/dL value to this unit, rounding mmol/L to one decimal
+    /// place.
+    pub fn convert(&self, mg_dl: f64) -> f64 {
+        match self {
+            OutUnits::MgDl => mg_dl,
+            OutUnits::MmolL => (mg_dl / 18.0 * 10.0).round() / 10.0,
+        }
+    }
+
+    /// Format a mg/dL value for display in this unit
+    pub fn format(&self, mg_dl: f64) -> String {
+        match self {
+            OutUnits::MgDl => format!("{:.0}", mg_dl),
+            OutUnits::MmolL => format!("{:.1}", self.convert(mg_dl)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mg_dl_passes_through_unchanged() {
+        assert_eq!(OutUnits::MgDl.convert(180.0), 180.0);
+        assert_eq!(OutUnits::MgDl.format(180.0), "180");
+    }
+
+    #[test]
+    fn mmol_l_divides_by_eighteen_and_rounds_to_one_decimal() {
+        assert_eq!(OutUnits::MmolL.convert(180.0), 10.0);
+        assert_eq!(OutUnits::MmolL.convert(100.0), 5.6);
+        assert_eq!(OutUnits::MmolL.format(100.0), "5.6");
+    }
+}
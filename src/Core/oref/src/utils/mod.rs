@@ -0,0 +1,9 @@
+//! Small numeric helpers shared across the determine-basal pipeline
+
+use crate::pump::PumpModel;
+
+/// Round a raw recommended basal rate (U/hr) to the nearest increment the
+/// given pump model can actually deliver. See [`PumpModel::round_rate`].
+pub fn round_basal(rate: f64, pump_model: PumpModel) -> f64 {
+    pump_model.round_rate(rate)
+}